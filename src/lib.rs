@@ -15,6 +15,13 @@
 //!     .with(RetryAfterMiddleware::new())
 //!     .build();
 //! ```
+//!
+//! By default, a response carrying a `Retry-After` header is handed back to the caller
+//! unretried, and the header's delay is only honored before the *next* request to the
+//! same URL. Call [`RetryAfterMiddleware::with_transient_retry`] to instead have the
+//! middleware wait out the delay and re-issue the same request in place, which is
+//! useful for `429`/`503` responses that the caller would otherwise have to retry by
+//! hand.
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_doc_code_examples)]
 
@@ -23,28 +30,280 @@ use std::{
     time::{Duration, SystemTime},
 };
 
-use http::{header::RETRY_AFTER, Extensions};
-use reqwest::Url;
+use http::{header::RETRY_AFTER, Extensions, HeaderMap, HeaderName};
+use reqwest::{StatusCode, Url};
 use reqwest_middleware::{
     reqwest::{Request, Response},
     Middleware, Next, Result,
 };
-use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+use time::{
+    format_description::{well_known::Rfc2822, FormatItem},
+    macros::format_description,
+    Date, Month, OffsetDateTime, PrimitiveDateTime,
+};
 use tokio::sync::RwLock;
 
+/// `Sun, 06 Nov 1994 08:49:37 GMT` — the preferred HTTP-date format (RFC 7231 §7.1.1.1).
+const IMF_FIXDATE: &[FormatItem<'_>] = format_description!(
+    "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
+
+/// `Sun Nov  6 08:49:37 1994` — the ANSI C `asctime()` format.
+const ASCTIME: &[FormatItem<'_>] = format_description!(
+    "[weekday repr:short] [month repr:short] [day padding:space] [hour]:[minute]:[second] [year]"
+);
+
+/// The status codes that [`RetryAfterMiddleware::with_transient_retry`] retries by default.
+const DEFAULT_RETRYABLE_STATUSES: &[StatusCode] = &[
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::SERVICE_UNAVAILABLE,
+];
+
+/// Configuration for retrying a request in place, set via
+/// [`RetryAfterMiddleware::with_transient_retry`].
+struct TransientRetryConfig {
+    max_attempts: u32,
+    retryable_statuses: Vec<StatusCode>,
+}
+
+/// Whether a rate-limit reset header carries an absolute or a relative timestamp.
+#[derive(Clone, Copy)]
+pub enum ResetKind {
+    /// The header holds Unix epoch seconds, e.g. GitHub's `X-RateLimit-Reset`.
+    AbsoluteEpochSeconds,
+    /// The header holds a delta in seconds from now, as in the IETF `RateLimit-Reset` draft.
+    RelativeSeconds,
+}
+
+/// A recognized pair of rate-limit headers: a "remaining" counter and the matching
+/// "reset" value to use once it hits zero.
+struct RateLimitHeaders {
+    remaining: HeaderName,
+    reset: HeaderName,
+    reset_kind: ResetKind,
+}
+
+impl RateLimitHeaders {
+    /// GitHub's `X-RateLimit-Remaining` / `X-RateLimit-Reset` (absolute epoch seconds).
+    fn github() -> Self {
+        Self {
+            remaining: HeaderName::from_static("x-ratelimit-remaining"),
+            reset: HeaderName::from_static("x-ratelimit-reset"),
+            reset_kind: ResetKind::AbsoluteEpochSeconds,
+        }
+    }
+
+    /// The IETF `RateLimit-Remaining` / `RateLimit-Reset` draft (delta-seconds).
+    fn ietf_draft() -> Self {
+        Self {
+            remaining: HeaderName::from_static("ratelimit-remaining"),
+            reset: HeaderName::from_static("ratelimit-reset"),
+            reset_kind: ResetKind::RelativeSeconds,
+        }
+    }
+}
+
+/// The granularity at which throttle state is keyed, set via
+/// [`RetryAfterMiddleware::with_key_granularity`].
+#[derive(Clone, Copy, Default)]
+pub enum KeyGranularity {
+    /// Key state on the full request URL, including path and query string (the default).
+    #[default]
+    FullUrl,
+    /// Key state on the request's origin (scheme, host, and port), so e.g.
+    /// `/items?page=1` and `/items?page=2` share a throttle.
+    Origin,
+}
+
+/// A storage backend for the wake-up timestamps learned from `Retry-After` (or
+/// rate-limit) headers, keyed by throttle key.
+///
+/// The default [`InMemoryStore`] is process-local; implement this trait to share
+/// throttle state across client instances or processes, e.g. via Redis or a file.
+#[async_trait::async_trait]
+pub trait RetryAfterStore: Send + Sync {
+    /// Returns the stored wake-up time for `key`, if any.
+    async fn get(&self, key: &Url) -> Option<SystemTime>;
+
+    /// Records a wake-up time for `key`.
+    async fn set(&self, key: Url, timestamp: SystemTime);
+
+    /// Clears any stored wake-up time for `key`.
+    async fn remove(&self, key: &Url);
+}
+
+/// The default, process-local [`RetryAfterStore`], backed by an in-memory map.
+#[derive(Default)]
+struct InMemoryStore {
+    entries: RwLock<HashMap<Url, SystemTime>>,
+}
+
+#[async_trait::async_trait]
+impl RetryAfterStore for InMemoryStore {
+    async fn get(&self, key: &Url) -> Option<SystemTime> {
+        self.entries.read().await.get(key).copied()
+    }
+
+    async fn set(&self, key: Url, timestamp: SystemTime) {
+        self.entries.write().await.insert(key, timestamp);
+    }
+
+    async fn remove(&self, key: &Url) {
+        self.entries.write().await.remove(key);
+    }
+}
+
 /// The `RetryAfterMiddleware` is a [`Middleware`] that adds support for the `Retry-After`
 /// header in [`reqwest`].
 pub struct RetryAfterMiddleware {
-    retry_after: RwLock<HashMap<Url, SystemTime>>,
+    retry_after: Box<dyn RetryAfterStore>,
+    transient_retry: Option<TransientRetryConfig>,
+    rate_limit_headers: Vec<RateLimitHeaders>,
+    key_granularity: KeyGranularity,
+    max_delay: Option<Duration>,
 }
 
 impl RetryAfterMiddleware {
     /// Creates a new `RetryAfterMiddleware`.
     pub fn new() -> Self {
         Self {
-            retry_after: RwLock::new(HashMap::new()),
+            retry_after: Box::new(InMemoryStore::default()),
+            transient_retry: None,
+            rate_limit_headers: vec![RateLimitHeaders::github(), RateLimitHeaders::ietf_draft()],
+            key_granularity: KeyGranularity::default(),
+            max_delay: None,
+        }
+    }
+
+    /// Starts building a `RetryAfterMiddleware`, for call sites that chain several
+    /// `with_*` configuration methods. Equivalent to [`RetryAfterMiddleware::new`].
+    pub fn builder() -> Self {
+        Self::new()
+    }
+
+    /// Uses `store` instead of the default in-memory map to persist `Retry-After`
+    /// wake-up times, e.g. to share throttle state across client instances.
+    pub fn with_store(mut self, store: impl RetryAfterStore + 'static) -> Self {
+        self.retry_after = Box::new(store);
+        self
+    }
+
+    /// Chooses the granularity at which throttle state is keyed; see [`KeyGranularity`].
+    pub fn with_key_granularity(mut self, granularity: KeyGranularity) -> Self {
+        self.key_granularity = granularity;
+        self
+    }
+
+    /// Caps how long a single `Retry-After` (or rate-limit) wait may be. A parsed
+    /// timestamp beyond `now + max_delay` is clamped down to the cap, guarding against
+    /// a malicious or buggy server parking requests indefinitely (e.g.
+    /// `Retry-After: 31536000`).
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Opts in to retrying the request itself, rather than only delaying the next one.
+    ///
+    /// When a response carries a `Retry-After` header together with a retryable status
+    /// (`429` and `503` by default, see [`Self::with_retryable_status`] to add more),
+    /// the middleware sleeps for the indicated duration and re-issues the same request,
+    /// up to `max_attempts` times, before returning the final response to the caller.
+    ///
+    /// The request body is cloned up front via [`Request::try_clone`]; if it can't be
+    /// cloned (e.g. it's a stream), the original response is returned unretried.
+    pub fn with_transient_retry(mut self, max_attempts: u32) -> Self {
+        self.transient_retry = Some(TransientRetryConfig {
+            max_attempts,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+        });
+        self
+    }
+
+    /// Registers an additional status code (e.g. `500`, `502`, `504`) as retryable.
+    ///
+    /// Has no effect unless [`Self::with_transient_retry`] has already been called.
+    pub fn with_retryable_status(mut self, status: StatusCode) -> Self {
+        if let Some(config) = &mut self.transient_retry {
+            config.retryable_statuses.push(status);
+        }
+        self
+    }
+
+    /// Registers an additional `remaining`/`reset` rate-limit header pair, for APIs
+    /// that signal throttling via custom header names instead of `Retry-After`.
+    ///
+    /// Responses are checked against every registered pair, in addition to the
+    /// built-in support for GitHub's `X-RateLimit-*` and the IETF `RateLimit-*` draft
+    /// headers, whenever `Retry-After` is absent from the response.
+    pub fn with_rate_limit_headers(
+        mut self,
+        remaining: HeaderName,
+        reset: HeaderName,
+        reset_kind: ResetKind,
+    ) -> Self {
+        self.rate_limit_headers.push(RateLimitHeaders {
+            remaining,
+            reset,
+            reset_kind,
+        });
+        self
+    }
+
+    /// Returns the key under which `url`'s throttle state is stored, per
+    /// [`Self::with_key_granularity`].
+    fn throttle_key(&self, url: &Url) -> Url {
+        match self.key_granularity {
+            KeyGranularity::FullUrl => url.clone(),
+            KeyGranularity::Origin => {
+                let mut origin = url.clone();
+                origin.set_path("");
+                origin.set_query(None);
+                origin.set_fragment(None);
+                origin
+            }
         }
     }
+
+    /// Clamps `timestamp` to `now + max_delay`, per [`Self::with_max_delay`].
+    fn clamp_delay(&self, timestamp: SystemTime) -> SystemTime {
+        match self.max_delay {
+            Some(max_delay) => timestamp.min(SystemTime::now() + max_delay),
+            None => timestamp,
+        }
+    }
+
+    /// Returns whether `response`'s status is one that transient-retry is configured
+    /// to retry.
+    fn is_retryable(&self, response: &Response) -> bool {
+        self.transient_retry
+            .as_ref()
+            .is_some_and(|config| config.retryable_statuses.contains(&response.status()))
+    }
+
+    /// Computes a wake-up time from the first registered rate-limit header pair that
+    /// appears in `headers` and indicates the limit has been exhausted.
+    fn rate_limit_reset(&self, headers: &HeaderMap) -> Option<SystemTime> {
+        self.rate_limit_headers.iter().find_map(|set| {
+            let remaining = headers.get(&set.remaining)?.to_str().ok()?;
+            if remaining != "0" {
+                return None;
+            }
+
+            let reset = headers
+                .get(&set.reset)?
+                .to_str()
+                .ok()?
+                .parse::<u64>()
+                .ok()?;
+            Some(match set.reset_kind {
+                ResetKind::AbsoluteEpochSeconds => {
+                    SystemTime::UNIX_EPOCH + Duration::from_secs(reset)
+                }
+                ResetKind::RelativeSeconds => SystemTime::now() + Duration::from_secs(reset),
+            })
+        })
+    }
 }
 
 impl Default for RetryAfterMiddleware {
@@ -60,9 +319,66 @@ fn parse_retry_value(val: &str) -> Option<SystemTime> {
     if let Ok(date) = OffsetDateTime::parse(val, &Rfc2822) {
         return Some(date.into());
     }
+    for format in [IMF_FIXDATE, ASCTIME] {
+        if let Ok(date) = PrimitiveDateTime::parse(val, format) {
+            return Some(date.assume_utc().into());
+        }
+    }
+    if let Some(date) = parse_rfc_850(val) {
+        return Some(date.into());
+    }
     None
 }
 
+/// Parses the obsolete RFC 850 `Retry-After` date form (`Sunday, 06-Nov-94 08:49:37 GMT`).
+///
+/// `time`'s format-description parser can't recover a two-digit year's century on its
+/// own (it has no default epoch to assume), so the century is resolved by hand here
+/// using the same `%y` heuristic applied to cookie dates (RFC 6265 §5.1.1): two-digit
+/// years `>= 70` are `19xx`, otherwise `20xx`.
+fn parse_rfc_850(val: &str) -> Option<OffsetDateTime> {
+    let val = val.strip_suffix(" GMT")?;
+    let (_, rest) = val.split_once(", ")?;
+    let (date, time) = rest.split_once(' ')?;
+
+    let mut date = date.split('-');
+    let day: u8 = date.next()?.parse().ok()?;
+    let month = month_from_short_name(date.next()?)?;
+    let two_digit_year: u8 = date.next()?.parse().ok()?;
+    let year = if two_digit_year >= 70 {
+        1900 + i32::from(two_digit_year)
+    } else {
+        2000 + i32::from(two_digit_year)
+    };
+
+    let mut time = time.splitn(3, ':');
+    let hour: u8 = time.next()?.parse().ok()?;
+    let minute: u8 = time.next()?.parse().ok()?;
+    let second: u8 = time.next()?.parse().ok()?;
+
+    let date = Date::from_calendar_date(year, month, day).ok()?;
+    Some(date.with_hms(hour, minute, second).ok()?.assume_utc())
+}
+
+/// Maps a three-letter English month abbreviation (`Jan`, `Feb`, …) to a [`Month`].
+fn month_from_short_name(name: &str) -> Option<Month> {
+    Some(match name {
+        "Jan" => Month::January,
+        "Feb" => Month::February,
+        "Mar" => Month::March,
+        "Apr" => Month::April,
+        "May" => Month::May,
+        "Jun" => Month::June,
+        "Jul" => Month::July,
+        "Aug" => Month::August,
+        "Sep" => Month::September,
+        "Oct" => Month::October,
+        "Nov" => Month::November,
+        "Dec" => Month::December,
+        _ => return None,
+    })
+}
+
 #[async_trait::async_trait]
 impl Middleware for RetryAfterMiddleware {
     async fn handle(
@@ -71,9 +387,9 @@ impl Middleware for RetryAfterMiddleware {
         extensions: &mut Extensions,
         next: Next<'_>,
     ) -> Result<Response> {
-        let url = req.url().clone();
+        let key = self.throttle_key(req.url());
 
-        if let Some(timestamp) = self.retry_after.read().await.get(&url) {
+        if let Some(timestamp) = self.retry_after.get(&key).await {
             let now = SystemTime::now();
 
             if let Ok(duration) = timestamp.duration_since(now) {
@@ -81,22 +397,65 @@ impl Middleware for RetryAfterMiddleware {
             }
         }
 
-        let res = next.run(req, extensions).await;
+        let mut attempts_left = self
+            .transient_retry
+            .as_ref()
+            .map_or(0, |config| config.max_attempts);
+        let mut pending_req = req;
+
+        let res = loop {
+            let retry_req = if attempts_left > 0 {
+                pending_req.try_clone()
+            } else {
+                None
+            };
+
+            let res = next.clone().run(pending_req, extensions).await;
+
+            let Some(retry_req) = retry_req else {
+                break res;
+            };
+
+            let should_retry = matches!(&res, Ok(response) if self.is_retryable(response));
+            if !should_retry {
+                break res;
+            }
+
+            let retry_after = res
+                .as_ref()
+                .ok()
+                .and_then(|response| response.headers().get(RETRY_AFTER))
+                .and_then(|val| val.to_str().ok())
+                .and_then(parse_retry_value)
+                .map(|timestamp| self.clamp_delay(timestamp));
+
+            let Some(timestamp) = retry_after else {
+                break res;
+            };
+
+            if let Ok(duration) = timestamp.duration_since(SystemTime::now()) {
+                tokio::time::sleep(duration).await;
+            }
+
+            attempts_left -= 1;
+            pending_req = retry_req;
+        };
 
         if let Ok(res) = &res {
-            match res.headers().get(RETRY_AFTER) {
-                Some(retry_after) => {
-                    if let Ok(val) = retry_after.to_str() {
-                        if let Some(timestamp) = parse_retry_value(val) {
-                            self.retry_after
-                                .write()
-                                .await
-                                .insert(url.clone(), timestamp);
-                        }
-                    }
+            let timestamp = res
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|val| val.to_str().ok())
+                .and_then(parse_retry_value)
+                .or_else(|| self.rate_limit_reset(res.headers()))
+                .map(|timestamp| self.clamp_delay(timestamp));
+
+            match timestamp {
+                Some(timestamp) => {
+                    self.retry_after.set(key.clone(), timestamp).await;
                 }
-                _ => {
-                    self.retry_after.write().await.remove(&url);
+                None => {
+                    self.retry_after.remove(&key).await;
                 }
             }
         }
@@ -115,9 +474,9 @@ mod test {
     use httpmock::{Method::GET, MockServer};
     use reqwest::Url;
     use reqwest_middleware::ClientBuilder;
-    use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+    use time::{format_description::well_known::Rfc2822, macros::datetime, OffsetDateTime};
 
-    use crate::RetryAfterMiddleware;
+    use crate::{parse_retry_value, KeyGranularity, RetryAfterMiddleware};
 
     #[tokio::test]
     async fn test() {
@@ -130,8 +489,6 @@ mod test {
             .with_arc(middleware.clone())
             .build();
 
-        test_empty_retry_after(&middleware).await;
-
         // create mock server
         let server = MockServer::start();
         let pre_ra_mock = server.mock(|when, then| {
@@ -150,6 +507,7 @@ mod test {
         });
 
         let url = Url::from_str(&server.url("/")).unwrap();
+        test_absent_retry_after(&middleware, &url).await;
 
         // hit URL; get RA value and store it
         let pre_test = SystemTime::now();
@@ -183,7 +541,194 @@ mod test {
         // this should have (1) slept and (2) cleared the stored RA afterward
         let post_test = SystemTime::now();
         assert!(post_test.duration_since(pre_test).unwrap() >= test_duration);
-        test_empty_retry_after(&middleware).await;
+        test_absent_retry_after(&middleware, &url).await;
+    }
+
+    #[tokio::test]
+    async fn test_transient_retry() {
+        let retry_duration = Duration::from_secs(1);
+        let max_attempts = 2;
+
+        let middleware = Arc::new(RetryAfterMiddleware::new().with_transient_retry(max_attempts));
+        let client = ClientBuilder::new(reqwest::Client::new())
+            .with_arc(middleware.clone())
+            .build();
+
+        // always responds 429, so the middleware should exhaust every attempt
+        let server = MockServer::start();
+        let throttled_mock = server.mock(|when, then| {
+            when.method(GET).path("/throttled");
+            then.status(429)
+                .header("Retry-After", retry_duration.as_secs().to_string())
+                .body("");
+        });
+
+        let url = Url::from_str(&server.url("/throttled")).unwrap();
+        let start = SystemTime::now();
+        let res = client.get(url).send().await.unwrap();
+
+        assert_eq!(res.status().as_u16(), 429);
+        assert_eq!(
+            throttled_mock.hits_async().await,
+            (max_attempts + 1) as usize
+        );
+        assert!(SystemTime::now().duration_since(start).unwrap() >= retry_duration * max_attempts);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_headers() {
+        let reset_at = SystemTime::now() + Duration::from_secs(2);
+        let reset_epoch = reset_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let middleware = Arc::new(RetryAfterMiddleware::new());
+        let client = ClientBuilder::new(reqwest::Client::new())
+            .with_arc(middleware.clone())
+            .build();
+
+        let server = MockServer::start();
+        let limited_mock = server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200)
+                .header("X-RateLimit-Remaining", "0")
+                .header("X-RateLimit-Reset", reset_epoch.to_string())
+                .body("");
+        });
+
+        let url = Url::from_str(&server.url("/")).unwrap();
+        client.get(url.clone()).send().await.unwrap();
+        limited_mock.assert_async().await;
+
+        let stored = middleware.retry_after.get(&url).await.unwrap();
+        let diff = stored
+            .duration_since(reset_at)
+            .unwrap_or_else(|e| e.duration());
+        assert!(diff <= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_custom_store() {
+        use std::{collections::HashMap, sync::Mutex};
+
+        use crate::RetryAfterStore;
+
+        #[derive(Default)]
+        struct RecordingStore {
+            entries: Mutex<HashMap<Url, SystemTime>>,
+        }
+
+        #[async_trait::async_trait]
+        impl RetryAfterStore for RecordingStore {
+            async fn get(&self, key: &Url) -> Option<SystemTime> {
+                self.entries.lock().unwrap().get(key).copied()
+            }
+
+            async fn set(&self, key: Url, timestamp: SystemTime) {
+                self.entries.lock().unwrap().insert(key, timestamp);
+            }
+
+            async fn remove(&self, key: &Url) {
+                self.entries.lock().unwrap().remove(key);
+            }
+        }
+
+        let test_duration = Duration::from_secs(1);
+        let middleware =
+            Arc::new(RetryAfterMiddleware::new().with_store(RecordingStore::default()));
+        let client = ClientBuilder::new(reqwest::Client::new())
+            .with_arc(middleware.clone())
+            .build();
+
+        let server = MockServer::start();
+        let ra_mock = server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200)
+                .header("Retry-After", test_duration.as_secs().to_string())
+                .body("");
+        });
+
+        let url = Url::from_str(&server.url("/")).unwrap();
+        let pre_test = SystemTime::now();
+        client.get(url.clone()).send().await.unwrap();
+        ra_mock.assert_async().await;
+
+        test_valid_retry_after(&middleware, &url, pre_test, test_duration).await;
+    }
+
+    #[tokio::test]
+    async fn test_origin_key_granularity() {
+        let test_duration = Duration::from_secs(2);
+        let middleware =
+            Arc::new(RetryAfterMiddleware::builder().with_key_granularity(KeyGranularity::Origin));
+        let client = ClientBuilder::new(reqwest::Client::new())
+            .with_arc(middleware.clone())
+            .build();
+
+        let server = MockServer::start();
+        let ra_mock = server.mock(|when, then| {
+            when.method(GET).path("/a");
+            then.status(200)
+                .header("Retry-After", test_duration.as_secs().to_string())
+                .body("");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/b");
+            then.status(200).body("");
+        });
+
+        let url_a = Url::from_str(&server.url("/a")).unwrap();
+        let url_b = Url::from_str(&server.url("/b")).unwrap();
+
+        let pre_test = SystemTime::now();
+        client.get(url_a).send().await.unwrap();
+        ra_mock.assert_async().await;
+
+        // a different path on the same origin shares the stored wait
+        client.get(url_b).send().await.unwrap();
+        assert!(SystemTime::now().duration_since(pre_test).unwrap() >= test_duration);
+    }
+
+    #[tokio::test]
+    async fn test_max_delay_clamp() {
+        let max_delay = Duration::from_millis(500);
+        let middleware = Arc::new(RetryAfterMiddleware::builder().with_max_delay(max_delay));
+        let client = ClientBuilder::new(reqwest::Client::new())
+            .with_arc(middleware.clone())
+            .build();
+
+        let server = MockServer::start();
+        let ra_mock = server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200).header("Retry-After", "31536000").body("");
+        });
+
+        let url = Url::from_str(&server.url("/")).unwrap();
+        let before = SystemTime::now();
+        client.get(url.clone()).send().await.unwrap();
+        ra_mock.assert_async().await;
+
+        let stored = middleware.retry_after.get(&url).await.unwrap();
+        assert!(stored.duration_since(before).unwrap() <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_http_date_formats() {
+        let expected: SystemTime = datetime!(1994-11-06 08:49:37 UTC).into();
+
+        assert_eq!(
+            parse_retry_value("Sun, 06 Nov 1994 08:49:37 GMT").unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_retry_value("Sunday, 06-Nov-94 08:49:37 GMT").unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_retry_value("Sun Nov  6 08:49:37 1994").unwrap(),
+            expected
+        );
     }
 
     #[tokio::test]
@@ -232,7 +777,7 @@ mod test {
         // this should have (1) slept and (2) cleared the stored RA afterward
         let duration = SystemTime::now().duration_since(begin.into()).unwrap();
         assert!(duration >= test_duration);
-        test_empty_retry_after(&middleware).await;
+        test_absent_retry_after(&middleware, &url).await;
     }
 
     async fn test_valid_retry_after(
@@ -241,22 +786,12 @@ mod test {
         now: SystemTime,
         test_duration: Duration,
     ) {
-        let time = middleware
-            .retry_after
-            .read()
-            .await
-            .get(url)
-            .cloned()
-            .unwrap();
+        let time = middleware.retry_after.get(url).await.unwrap();
         let duration = time.duration_since(now).unwrap();
         assert!(duration >= test_duration);
     }
 
     async fn test_absent_retry_after(middleware: &Arc<RetryAfterMiddleware>, url: &Url) {
-        assert!(middleware.retry_after.read().await.get(url).is_none());
-    }
-
-    async fn test_empty_retry_after(middleware: &Arc<RetryAfterMiddleware>) {
-        assert!(middleware.retry_after.read().await.is_empty());
+        assert!(middleware.retry_after.get(url).await.is_none());
     }
 }